@@ -1,11 +1,18 @@
 use std::borrow::Cow;
+use std::path::PathBuf;
 
-use crate::{config::Config, error, Auditor, Result};
+use crate::{
+    cache::Cache, config::Config, error, http, output::OutputFormat, Auditor, Result, Transport,
+};
 
 use snafu::*;
 
 const GITHUB_AUTH_ENV_KEY: &str = "GITHUB_AUTH_KEY";
 
+/// The default API base URL, for github.com. Override with
+/// [`AuditorBuilder::api_base_url`] to audit a GitHub Enterprise instance.
+const DEFAULT_API_BASE_URL: &str = "https://api.github.com";
+
 /// A builder struct for the `Auditor`. Allows you to configure GitHub
 /// organisation, underlying http client, audit configuration, and
 /// authentication token.
@@ -14,6 +21,13 @@ pub struct AuditorBuilder<'a> {
     client: Option<Cow<'a, reqwest::Client>>,
     config: Config,
     org: String,
+    output_format: OutputFormat,
+    deny_warnings: bool,
+    concurrency: usize,
+    cache_dir: Option<PathBuf>,
+    api_base_url: String,
+    ca_cert: Option<PathBuf>,
+    transport: Transport,
 }
 
 impl<'a> AuditorBuilder<'a> {
@@ -22,8 +36,10 @@ impl<'a> AuditorBuilder<'a> {
     /// ```
     /// use gh_auditor::{Auditor, AuditorBuilder};
     ///
-    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// let auditor: Auditor = AuditorBuilder::new("rust-lang").finish()?;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let auditor: Auditor = AuditorBuilder::new("rust-lang").finish().await?;
+    /// # Ok(())
     /// # }
     /// ```
     pub fn new<I: Into<String>>(org: I) -> Self {
@@ -32,6 +48,13 @@ impl<'a> AuditorBuilder<'a> {
             client: None,
             config: Config::default(),
             auth_key: None,
+            output_format: OutputFormat::default(),
+            deny_warnings: false,
+            concurrency: http::DEFAULT_CONCURRENCY,
+            cache_dir: None,
+            api_base_url: DEFAULT_API_BASE_URL.to_owned(),
+            ca_cert: None,
+            transport: Transport::default(),
         }
     }
 
@@ -56,27 +79,100 @@ impl<'a> AuditorBuilder<'a> {
         self
     }
 
+    /// Sets the format audit results should be rendered in, for machine
+    /// consumption by CI systems. (Default: `OutputFormat::Human`)
+    pub fn output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Sets whether warning-level findings (e.g. admins with commit
+    /// activity) should be treated as failures, in addition to error-level
+    /// findings (e.g. disabled 2FA). (Default: `false`)
+    pub fn deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
+    /// Sets the maximum number of requests to have in flight at once when
+    /// fanning out across a paginated resource's pages. Clamped to at least
+    /// `1`: a concurrency of `0` would leave [`tokio::sync::Semaphore`]
+    /// unable to ever grant a permit, deadlocking every paginated request.
+    /// (Default: `32`)
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Enables an on-disk cache of responses at `dir`, keyed by URL, so
+    /// repeated audits of the same organisation can use conditional
+    /// requests (`If-None-Match`) instead of re-downloading unchanged data.
+    /// (Default: disabled)
+    pub fn cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the base URL requests are made against, for auditing a GitHub
+    /// Enterprise instance instead of github.com. (Default:
+    /// `https://api.github.com`)
+    pub fn api_base_url<I: Into<String>>(mut self, api_base_url: I) -> Self {
+        self.api_base_url = api_base_url.into();
+        self
+    }
+
+    /// Sets a custom CA certificate (PEM-encoded) to trust when making
+    /// requests, for GitHub Enterprise instances behind a certificate not in
+    /// the system trust store. Ignored if a custom [`Self::client`] is set.
+    /// (Default: none, use the system trust store)
+    pub fn ca_cert(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_cert = Some(path.into());
+        self
+    }
+
+    /// Sets which API organisation-wide auditing data (admins, members,
+    /// branch protection) is fetched from. (Default: [`Transport::GraphQl`])
+    ///
+    /// [`Transport::Rest`] is a slower fallback, useful against GitHub
+    /// Enterprise instances too old to support the GraphQL API.
+    pub fn transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
     /// Consume `AuditorBuilder` and construct `Auditor` with configuration or
     /// default values, and query for organisation information.
     /// # Errors
     /// If no authentication key was not provided or invalid.
-    pub fn finish(self) -> Result<Auditor<'a>> {
+    pub async fn finish(self) -> Result<Auditor<'a>> {
         let auth_key = self
             .auth_key
             .or_else(|| std::env::var(GITHUB_AUTH_ENV_KEY).ok())
             .context(error::NoAuthKey)?;
 
-        let client = self
-            .client
-            .unwrap_or_else(|| Cow::Owned(reqwest::Client::new()));
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder();
+                if let Some(ca_cert) = &self.ca_cert {
+                    let pem = std::fs::read(ca_cert).context(error::CaCertIo { path: ca_cert })?;
+                    let cert = reqwest::Certificate::from_pem(&pem).context(error::Http)?;
+                    builder = builder.add_root_certificate(cert);
+                }
+                Cow::Owned(builder.build().context(error::Http)?)
+            }
+        };
+
+        let cache = self.cache_dir.map(Cache::new);
 
-        let organisation = client
-            .get(&format!("https://api.github.com/orgs/{}", self.org))
-            .bearer_auth(&auth_key)
-            .send()
-            .context(error::Http)?
-            .json()
-            .context(error::Http)?;
+        let organisation = http::fetch_page(
+            &client,
+            &auth_key,
+            &format!("{}/orgs/{}", self.api_base_url, self.org),
+            cache.as_ref(),
+        )
+        .await?
+        .body;
 
         Ok(Auditor {
             auth_key,
@@ -84,6 +180,12 @@ impl<'a> AuditorBuilder<'a> {
             config: self.config,
             organisation,
             has_run_audit: false,
+            output_format: self.output_format,
+            deny_warnings: self.deny_warnings,
+            concurrency: self.concurrency,
+            cache,
+            api_base_url: self.api_base_url,
+            transport: self.transport,
         })
     }
 }
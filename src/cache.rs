@@ -0,0 +1,72 @@
+//! An on-disk cache of GitHub API responses, keyed by URL, so repeated
+//! audits of the same organisation can use conditional requests
+//! (`If-None-Match`) instead of re-downloading unchanged resources.
+//!
+//! GitHub doesn't count a `304 Not Modified` response against the primary
+//! rate limit, so this directly increases how much of a large org can be
+//! audited per hour.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// A cached response body for a single URL, alongside the `ETag` GitHub
+/// returned it with.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CachedPage {
+    /// The `ETag` to send as `If-None-Match` on the next request.
+    pub(crate) etag: String,
+    /// The `Link` header the response was returned with, if any, so
+    /// pagination still works when a page is served from cache.
+    pub(crate) link: Option<String>,
+    /// The cached JSON body.
+    pub(crate) body: serde_json::Value,
+}
+
+/// An on-disk cache of [`CachedPage`]s, keyed by URL.
+#[derive(Debug)]
+pub(crate) struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Create a cache backed by `dir`. The directory is created lazily, the
+    /// first time an entry is stored.
+    pub(crate) fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Load the cached entry for `url`, if one exists and is readable.
+    pub(crate) fn load(&self, url: &str) -> Option<CachedPage> {
+        let bytes = std::fs::read(self.path_for(url)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Store `page` as the cached entry for `url`, overwriting any existing
+    /// entry. Failures are logged and otherwise ignored, since the cache is
+    /// purely an optimisation.
+    pub(crate) fn store(&self, url: &str, page: &CachedPage) {
+        if let Err(error) = std::fs::create_dir_all(&self.dir) {
+            log::warn!("Could not create cache directory: {}", error);
+            return;
+        }
+
+        match serde_json::to_vec(page) {
+            Ok(bytes) => {
+                if let Err(error) = std::fs::write(self.path_for(url), bytes) {
+                    log::warn!("Could not write cache entry: {}", error);
+                }
+            }
+            Err(error) => log::warn!("Could not serialize cache entry: {}", error),
+        }
+    }
+
+    /// The on-disk path a `url`'s cache entry is stored at.
+    fn path_for(&self, url: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        self.dir.join(format!("{:x}.json", hasher.finish()))
+    }
+}
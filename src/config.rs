@@ -1,5 +1,14 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use crate::{error, Result};
+
 /// A struct configuring which audits the `Auditor` should run.
-#[derive(Debug, Clone, Hash, PartialEq)]
+#[derive(Debug, Clone, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     // Toggles
     /// Warns if the organisation requires 2 factor authenication for all
@@ -42,3 +51,25 @@ impl Default for Config {
         }
     }
 }
+
+impl Config {
+    /// Load a `Config` from a declarative policy file at `path`, in either
+    /// TOML or YAML, chosen by its file extension (`.toml`, or `.yml`/
+    /// `.yaml`). Fields not present in the file fall back to
+    /// `Config::default()`.
+    /// # Errors
+    /// If `path` cannot be read, has an extension other than `toml`, `yml`,
+    /// or `yaml`, or its contents cannot be parsed into a `Config`.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).context(error::ConfigIo { path })?;
+
+        match path.extension().and_then(OsStr::to_str) {
+            Some("toml") => toml::from_str(&contents).context(error::InvalidTomlConfig { path }),
+            Some("yml") | Some("yaml") => {
+                serde_yaml::from_str(&contents).context(error::InvalidYamlConfig { path })
+            }
+            _ => error::UnknownConfigFormat { path }.fail(),
+        }
+    }
+}
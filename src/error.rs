@@ -24,11 +24,54 @@ pub enum Error {
         backtrace: snafu::Backtrace,
     },
 
+    /// The GraphQL API reported an error in its response body, even though
+    /// the HTTP response itself was a 200 (e.g. a malformed query or
+    /// insufficient scopes).
+    #[snafu(display("Error from GitHub GraphQL API: {}", message))]
+    GraphQl { message: String },
+
+    /// Error occurred while reading the custom CA certificate from disk.
+    #[snafu(display("Error reading CA certificate at {}: {}\n\n{}\n", path.display(), source, backtrace))]
+    CaCertIo {
+        path: std::path::PathBuf,
+        backtrace: snafu::Backtrace,
+        source: std::io::Error,
+    },
+
     #[snafu(display("{}", kind))]
     Audit { kind: AuditError },
 
     #[snafu(display("Unexpected key missing from GitHub data."))]
     MissingGitHubData,
+
+    /// Error occurred while reading a policy file from disk.
+    #[snafu(display("Error reading config at {}: {}\n\n{}\n", path.display(), source, backtrace))]
+    ConfigIo {
+        path: std::path::PathBuf,
+        backtrace: snafu::Backtrace,
+        source: std::io::Error,
+    },
+
+    /// A policy file's extension wasn't `toml`, `yml`, or `yaml`.
+    #[snafu(display(
+        "Unrecognised config format for {}: expected a `.toml`, `.yml`, or `.yaml` extension.",
+        path.display(),
+    ))]
+    UnknownConfigFormat { path: std::path::PathBuf },
+
+    /// Error occurred while parsing a TOML policy file.
+    #[snafu(display("Error parsing TOML config at {}: {}\n", path.display(), source))]
+    InvalidTomlConfig {
+        path: std::path::PathBuf,
+        source: toml::de::Error,
+    },
+
+    /// Error occurred while parsing a YAML policy file.
+    #[snafu(display("Error parsing YAML config at {}: {}\n", path.display(), source))]
+    InvalidYamlConfig {
+        path: std::path::PathBuf,
+        source: serde_yaml::Error,
+    },
 }
 
 impl Error {
@@ -50,6 +93,132 @@ pub enum AuditError {
     NoAuditsRan,
     /// A list of repositories that have unprotected master branches.
     UnProtectedMasterBranches(Vec<serde_json::Value>),
+    /// A list of installed application slugs that aren't in
+    /// `installed_app_whitelist`.
+    UnexpectedApps(Vec<String>),
+    /// A list of application slugs in `installed_app_whitelist` that aren't
+    /// installed in the organisation.
+    MissingApps(Vec<String>),
+    /// A list of admin logins that aren't in `admin_whitelist`.
+    UnexpectedAdmins(Vec<String>),
+    /// A list of admin logins in `admin_whitelist` that aren't admins of the
+    /// organisation.
+    MissingAdmins(Vec<String>),
+    /// A list of member logins that aren't in `member_whitelist`.
+    UnexpectedMembers(Vec<String>),
+    /// A list of member logins in `member_whitelist` that aren't members of
+    /// the organisation.
+    MissingMembers(Vec<String>),
+}
+
+impl AuditError {
+    /// The stable identifier for the audit that produces this error, used
+    /// in machine-readable output.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::AdminsHaveCommits(_) => "admins_have_no_commit_activity",
+            Self::Disabled2Fa => "enforces_2fa",
+            Self::NoAuditsRan => "no_audits_ran",
+            Self::UnProtectedMasterBranches(_) => "all_repos_master_is_protected",
+            Self::UnexpectedApps(_) | Self::MissingApps(_) => "installed_app_whitelist",
+            Self::UnexpectedAdmins(_) | Self::MissingAdmins(_) => "admin_whitelist",
+            Self::UnexpectedMembers(_) | Self::MissingMembers(_) => "member_whitelist",
+        }
+    }
+
+    /// How severe this finding is, used to decide machine-readable severity
+    /// levels and exit-code policy.
+    pub fn severity(&self) -> crate::output::Severity {
+        use crate::output::Severity;
+
+        match self {
+            Self::AdminsHaveCommits(_) => Severity::Warning,
+            Self::Disabled2Fa => Severity::Error,
+            Self::NoAuditsRan => Severity::Error,
+            Self::UnProtectedMasterBranches(_) => Severity::Warning,
+            Self::UnexpectedApps(_)
+            | Self::MissingApps(_)
+            | Self::UnexpectedAdmins(_)
+            | Self::MissingAdmins(_)
+            | Self::UnexpectedMembers(_)
+            | Self::MissingMembers(_) => Severity::Warning,
+        }
+    }
+
+    /// The offending entities (admin logins, repository `full_name`s, etc.)
+    /// collected while running the audit.
+    fn entities(&self) -> Vec<String> {
+        match self {
+            Self::AdminsHaveCommits(admins) => admins
+                .iter()
+                .filter_map(|v| v.get("login").and_then(|v| v.as_str()))
+                .map(str::to_owned)
+                .collect(),
+            Self::UnProtectedMasterBranches(repos) => repos
+                .iter()
+                .filter_map(|v| v.get("full_name").and_then(|v| v.as_str()))
+                .map(str::to_owned)
+                .collect(),
+            Self::Disabled2Fa | Self::NoAuditsRan => Vec::new(),
+            Self::UnexpectedApps(entities)
+            | Self::MissingApps(entities)
+            | Self::UnexpectedAdmins(entities)
+            | Self::MissingAdmins(entities)
+            | Self::UnexpectedMembers(entities)
+            | Self::MissingMembers(entities) => entities.clone(),
+        }
+    }
+
+    /// The recommendation to resolve this finding, as shown in `Display`.
+    pub fn recommendation(&self) -> &'static str {
+        match self {
+            Self::AdminsHaveCommits(_) => {
+                "Create seperate accounts for administration access to \
+                 the organisation."
+            }
+            Self::Disabled2Fa => "Enable 2 Factor as a requirement for members.",
+            Self::NoAuditsRan => "Adjust your configuration to enable some of audit procedures.",
+            Self::UnProtectedMasterBranches(_) => {
+                "Protect master branches and require all commits are made \
+                 through PRs."
+            }
+            Self::UnexpectedApps(_) => {
+                "Review the installed applications and either uninstall them \
+                 or add them to `installed_app_whitelist`."
+            }
+            Self::MissingApps(_) => {
+                "Install the missing applications, or remove them from \
+                 `installed_app_whitelist` if they are no longer required."
+            }
+            Self::UnexpectedAdmins(_) => {
+                "Review the admins and either revoke their access or add \
+                 them to `admin_whitelist`."
+            }
+            Self::MissingAdmins(_) => {
+                "Grant admin access to the missing users, or remove them \
+                 from `admin_whitelist` if they are no longer required."
+            }
+            Self::UnexpectedMembers(_) => {
+                "Review the members and either remove them from the \
+                 organisation or add them to `member_whitelist`."
+            }
+            Self::MissingMembers(_) => {
+                "Invite the missing users, or remove them from \
+                 `member_whitelist` if they are no longer required."
+            }
+        }
+    }
+
+    /// Convert this error into a structured [`crate::output::Finding`] for
+    /// machine-readable output.
+    pub fn to_finding(&self) -> crate::output::Finding {
+        crate::output::Finding {
+            id: self.id(),
+            severity: self.severity(),
+            entities: self.entities(),
+            recommendation: self.recommendation(),
+        }
+    }
 }
 
 impl fmt::Display for AuditError {
@@ -80,26 +249,38 @@ impl fmt::Display for AuditError {
                     .collect::<Vec<_>>()
                     .join(" ")
             ),
-        };
 
-        let recommendation = match self {
-            Self::AdminsHaveCommits(_) => {
-                "Create seperate accounts for administration access to \
-                 the organisation."
-            }
-            Self::Disabled2Fa => "Enable 2 Factor as a requirement for members.",
-            Self::NoAuditsRan => "Adjust your configuration to enable some of audit procedures.",
-            Self::UnProtectedMasterBranches(_) => {
-                "Protect master branches and require all commits are made \
-                 through PRs."
-            }
+            Self::UnexpectedApps(apps) => format!(
+                "Applications ({}) are installed but not in `installed_app_whitelist`.",
+                apps.join(" ")
+            ),
+            Self::MissingApps(apps) => format!(
+                "Applications ({}) are in `installed_app_whitelist` but not installed.",
+                apps.join(" ")
+            ),
+            Self::UnexpectedAdmins(admins) => format!(
+                "Admins ({}) are not in `admin_whitelist`.",
+                admins.join(" ")
+            ),
+            Self::MissingAdmins(admins) => format!(
+                "Admins ({}) are in `admin_whitelist` but aren't admins of the organisation.",
+                admins.join(" ")
+            ),
+            Self::UnexpectedMembers(members) => format!(
+                "Members ({}) are not in `member_whitelist`.",
+                members.join(" ")
+            ),
+            Self::MissingMembers(members) => format!(
+                "Members ({}) are in `member_whitelist` but aren't members of the organisation.",
+                members.join(" ")
+            ),
         };
 
         writeln!(
             f,
             "‚ùóÔ∏è Warning:\n{warn}\n\nüí° Recommendation:\n{recommendation}",
             warn = warn,
-            recommendation = recommendation
+            recommendation = self.recommendation()
         )
     }
 }
@@ -0,0 +1,218 @@
+//! A single GraphQL query for organisation-wide auditing data, used in
+//! place of the separate REST requests that `audit_2fa`,
+//! `audit_admin_commit_activity`, `audit_all_master_branches_are_protected`,
+//! `audit_admins`, and `audit_members` used to fan out across on their own:
+//! the 2FA requirement, member roles, and every repository's
+//! default-branch protection come back from one query, cutting the request
+//! (and rate-limit) cost of auditing a large organisation from
+//! O(members + repos) down to O(pages).
+
+use snafu::OptionExt;
+
+use crate::{error, http, Result};
+
+/// Member roles and repository branch protection, paginated independently
+/// via `$membersCursor` and `$reposCursor` so a page is only re-requested
+/// for whichever of the two still has pages left.
+const ORG_QUERY: &str = r#"
+query($org: String!, $membersCursor: String, $reposCursor: String) {
+  organization(login: $org) {
+    requiresTwoFactorAuthentication
+    membersWithRole(first: 100, after: $membersCursor) {
+      edges {
+        role
+        node { login }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+    repositories(first: 100, after: $reposCursor) {
+      nodes {
+        nameWithOwner
+        defaultBranchRef {
+          name
+          branchProtectionRule { id }
+        }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}
+"#;
+
+/// Organisation members and repository branch protection, fetched by
+/// [`fetch_org_data`].
+#[derive(Debug, Default)]
+pub(crate) struct OrgData {
+    /// Whether the organisation requires 2FA of its members, to match the
+    /// REST org blob's `two_factor_requirement_enabled`.
+    pub(crate) requires_two_factor_authentication: bool,
+    /// Every member with the `ADMIN` role, as `{"login": ...}`, to match
+    /// the shape the REST listings they replaced returned.
+    pub(crate) admins: Vec<serde_json::Value>,
+    /// Every member, admin or not, as `{"login": ...}`.
+    pub(crate) members: Vec<serde_json::Value>,
+    /// Every repository whose default branch is named `master` and has no
+    /// protection rule, as `{"full_name": ...}`.
+    pub(crate) unprotected_repos: Vec<serde_json::Value>,
+}
+
+/// Fetch the 2FA requirement, every member (with role), and every
+/// repository (with default-branch protection) of `org` from the GraphQL
+/// API at `{api_base_url}/graphql`.
+pub(crate) async fn fetch_org_data(
+    client: &reqwest::Client,
+    auth_key: &str,
+    api_base_url: &str,
+    org: &str,
+) -> Result<OrgData> {
+    let url = format!("{}/graphql", api_base_url);
+    let mut data = OrgData::default();
+
+    let mut members_cursor: Option<String> = None;
+    let mut repos_cursor: Option<String> = None;
+    let mut members_has_next = true;
+    let mut repos_has_next = true;
+
+    while members_has_next || repos_has_next {
+        let variables = serde_json::json!({
+            "org": org,
+            "membersCursor": members_cursor,
+            "reposCursor": repos_cursor,
+        });
+
+        let response = http::send_graphql(client, auth_key, &url, ORG_QUERY, variables).await?;
+
+        if let Some(message) = graphql_error_message(&response) {
+            return error::GraphQl { message }.fail();
+        }
+
+        let organization = response
+            .get("data")
+            .and_then(|data| data.get("organization"))
+            .context(error::MissingGitHubData)?;
+
+        data.requires_two_factor_authentication = organization
+            .get("requiresTwoFactorAuthentication")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        if members_has_next {
+            let members_with_role = organization
+                .get("membersWithRole")
+                .context(error::MissingGitHubData)?;
+
+            for edge in members_with_role
+                .get("edges")
+                .and_then(serde_json::Value::as_array)
+                .context(error::MissingGitHubData)?
+            {
+                let role = edge.get("role").and_then(serde_json::Value::as_str);
+                let login = edge
+                    .get("node")
+                    .and_then(|node| node.get("login"))
+                    .and_then(serde_json::Value::as_str);
+
+                let (role, login) = match (role, login) {
+                    (Some(role), Some(login)) => (role, login),
+                    _ => continue,
+                };
+
+                let entry = serde_json::json!({ "login": login });
+                if role == "ADMIN" {
+                    data.admins.push(entry.clone());
+                }
+                data.members.push(entry);
+            }
+
+            let page_info = members_with_role
+                .get("pageInfo")
+                .context(error::MissingGitHubData)?;
+            members_has_next = page_info
+                .get("hasNextPage")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            members_cursor = page_info
+                .get("endCursor")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_owned);
+        }
+
+        if repos_has_next {
+            let repositories = organization
+                .get("repositories")
+                .context(error::MissingGitHubData)?;
+
+            for repo in repositories
+                .get("nodes")
+                .and_then(serde_json::Value::as_array)
+                .context(error::MissingGitHubData)?
+            {
+                let full_name = match repo
+                    .get("nameWithOwner")
+                    .and_then(serde_json::Value::as_str)
+                {
+                    Some(full_name) => full_name,
+                    None => continue,
+                };
+
+                let default_branch = match repo.get("defaultBranchRef") {
+                    Some(branch) if !branch.is_null() => branch,
+                    // No default branch at all (e.g. an empty repository):
+                    // there's nothing to protect, so don't flag it.
+                    _ => continue,
+                };
+
+                let is_master = default_branch
+                    .get("name")
+                    .and_then(serde_json::Value::as_str)
+                    .map(|name| name == "master")
+                    .unwrap_or(false);
+
+                // Only a default branch named `master` is audited; repos
+                // whose default branch is named something else aren't
+                // flagged, even if they also have a non-default `master`
+                // branch (which the REST fallback does check).
+                if !is_master {
+                    continue;
+                }
+
+                let is_protected = default_branch
+                    .get("branchProtectionRule")
+                    .map(|rule| !rule.is_null())
+                    .unwrap_or(false);
+
+                if !is_protected {
+                    data.unprotected_repos
+                        .push(serde_json::json!({ "full_name": full_name }));
+                }
+            }
+
+            let page_info = repositories
+                .get("pageInfo")
+                .context(error::MissingGitHubData)?;
+            repos_has_next = page_info
+                .get("hasNextPage")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+            repos_cursor = page_info
+                .get("endCursor")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_owned);
+        }
+    }
+
+    Ok(data)
+}
+
+/// The message of the first entry in a GraphQL response's top-level
+/// `errors` array, if any. The GraphQL API reports query errors this way
+/// even on a `200 OK` response.
+fn graphql_error_message(response: &serde_json::Value) -> Option<String> {
+    response
+        .get("errors")
+        .and_then(serde_json::Value::as_array)
+        .and_then(|errors| errors.first())
+        .and_then(|error| error.get("message"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_owned)
+}
@@ -0,0 +1,251 @@
+//! Low-level HTTP helpers: retrying REST and GraphQL requests that hit
+//! GitHub's rate limits or transient server errors, serving conditional
+//! REST requests from an on-disk cache, and deriving the set of page URLs
+//! from a paginated REST resource's `Link` header.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyperx::header::{RelationType, TypedHeaders};
+use rand::Rng;
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use snafu::ResultExt;
+
+use crate::cache::{Cache, CachedPage};
+use crate::{error, Result};
+
+/// The default number of requests allowed in flight at once when fanning
+/// out across a resource's pages.
+pub(crate) const DEFAULT_CONCURRENCY: usize = 32;
+
+/// The longest we'll ever sleep for between retries, regardless of what a
+/// rate limit reset or backoff schedule asks for.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// The base delay for the exponential backoff applied to transient server
+/// errors.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// The number of times a transient server error will be retried before
+/// giving up and returning it to the caller.
+const MAX_RETRIES: u32 = 5;
+
+/// A single fetched page of a GitHub resource.
+pub(crate) struct Page {
+    /// The parsed JSON body, either freshly fetched or served from cache on
+    /// a `304 Not Modified`.
+    pub(crate) body: serde_json::Value,
+    /// The URL of the `last` page of the resource, if `body` has more than
+    /// one page.
+    pub(crate) link: Option<String>,
+}
+
+/// Send an authenticated `GET` to `url`, retrying on rate limiting (with a
+/// wait until `X-RateLimit-Reset`) and transient 5xx errors (with jittered
+/// exponential backoff).
+///
+/// If `cache` is set, the request is sent with `If-None-Match` set to the
+/// previous response's `ETag`, if one was cached; on a `304 Not Modified`
+/// the cached body is returned instead of re-downloading it, and on a fresh
+/// response the new body is stored back into the cache for next time.
+pub(crate) async fn fetch_page(
+    client: &reqwest::Client,
+    auth_key: &str,
+    url: &str,
+    cache: Option<&Cache>,
+) -> Result<Page> {
+    let cached = cache.and_then(|cache| cache.load(url));
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 0.. {
+        let mut request = client.get(url).bearer_auth(auth_key);
+        if let Some(cached) = &cached {
+            request = request.header(IF_NONE_MATCH, &cached.etag);
+        }
+
+        let response = request.send().await.context(error::Http)?;
+
+        if is_rate_limited(&response) {
+            log::warn!("Rate limited by GitHub, waiting until it resets: {}", url);
+            tokio::time::sleep(rate_limit_delay(&response)).await;
+            continue;
+        }
+
+        if response.status().is_server_error() && attempt < MAX_RETRIES {
+            tokio::time::sleep(jitter(backoff)).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            continue;
+        }
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                log::debug!("Serving {} from cache (304 Not Modified)", url);
+                return Ok(Page {
+                    body: cached.body,
+                    link: cached.link,
+                });
+            }
+        }
+
+        let link = last_page_url(response.headers());
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned);
+
+        let body = response
+            .json::<serde_json::Value>()
+            .await
+            .context(error::Http)?;
+
+        if let (Some(cache), Some(etag)) = (cache, etag) {
+            cache.store(
+                url,
+                &CachedPage {
+                    etag,
+                    link: link.clone(),
+                    body: body.clone(),
+                },
+            );
+        }
+
+        return Ok(Page { body, link });
+    }
+
+    unreachable!("retry loop only exits via return")
+}
+
+/// Send a GraphQL `query` with `variables` to `url`, retrying on rate
+/// limiting and transient 5xx errors exactly as [`fetch_page`] does.
+///
+/// Unlike REST responses, GraphQL responses aren't cached: they're never
+/// returned with an `ETag` to make a conditional request against.
+pub(crate) async fn send_graphql(
+    client: &reqwest::Client,
+    auth_key: &str,
+    url: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> Result<serde_json::Value> {
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 0.. {
+        let response = client
+            .post(url)
+            .bearer_auth(auth_key)
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await
+            .context(error::Http)?;
+
+        if is_rate_limited(&response) {
+            log::warn!("Rate limited by GitHub, waiting until it resets: {}", url);
+            tokio::time::sleep(rate_limit_delay(&response)).await;
+            continue;
+        }
+
+        if response.status().is_server_error() && attempt < MAX_RETRIES {
+            tokio::time::sleep(jitter(backoff)).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            continue;
+        }
+
+        return response
+            .json::<serde_json::Value>()
+            .await
+            .context(error::Http);
+    }
+
+    unreachable!("retry loop only exits via return")
+}
+
+/// Whether `response` indicates we've exhausted GitHub's rate limit.
+fn is_rate_limited(response: &reqwest::Response) -> bool {
+    let status = response.status();
+    let out_of_requests = response
+        .headers()
+        .get("x-ratelimit-remaining")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(|remaining| remaining == 0)
+        .unwrap_or(false);
+
+    (status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS) && out_of_requests
+}
+
+/// How long to sleep before retrying a rate-limited request, based on
+/// `X-RateLimit-Reset`, capped to `MAX_BACKOFF`.
+fn rate_limit_delay(response: &reqwest::Response) -> Duration {
+    let reset_at = response
+        .headers()
+        .get("x-ratelimit-reset")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(0);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    Duration::from_secs(reset_at.saturating_sub(now)).min(MAX_BACKOFF)
+}
+
+/// Add +/-20% jitter to `duration`, to avoid every in-flight request
+/// retrying in lockstep.
+fn jitter(duration: Duration) -> Duration {
+    let factor = rand::thread_rng().gen_range(0.8, 1.2);
+    duration.mul_f64(factor)
+}
+
+/// The URL of the `last` page of a paginated resource, if `headers` has a
+/// `Link` header with more than one page.
+pub(crate) fn last_page_url(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    headers
+        .decode::<hyperx::header::Link>()
+        .ok()
+        .and_then(|link| {
+            link.values()
+                .iter()
+                .find(|value| {
+                    value
+                        .rel()
+                        .map(|rel| rel.contains(&RelationType::Last))
+                        .unwrap_or(false)
+                })
+                .map(|value| value.link().to_owned())
+        })
+}
+
+/// Extract the `page` query parameter from a GitHub pagination URL.
+pub(crate) fn page_number(url: &str) -> Option<u64> {
+    reqwest::Url::parse(url)
+        .ok()?
+        .query_pairs()
+        .find(|(key, _)| key == "page")
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+/// Rewrite `url`'s `page` query parameter to `page`, keeping every other
+/// query parameter (and the filters GitHub already applied, such as
+/// `role=admin`) intact.
+pub(crate) fn with_page(url: &str, page: u64) -> Option<String> {
+    let mut parsed = reqwest::Url::parse(url).ok()?;
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| key != "page")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    {
+        let mut query = parsed.query_pairs_mut();
+        query.clear();
+        for (key, value) in &pairs {
+            query.append_pair(key, value);
+        }
+        query.append_pair("page", &page.to_string());
+    }
+
+    Some(parsed.into_string())
+}
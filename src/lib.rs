@@ -2,19 +2,46 @@
 #![warn(missing_docs)]
 
 mod builder;
+mod cache;
 mod config;
 mod error;
+mod graphql;
+mod http;
+mod output;
 
 pub use builder::AuditorBuilder;
+pub use config::Config;
+pub use output::{to_json, to_sarif, Finding, OutputFormat, Severity};
 
 use std::borrow::Cow;
 
-use hyperx::header::{RelationType, TypedHeaders};
+use futures::stream::{FuturesUnordered, StreamExt};
 use snafu::{OptionExt, ResultExt};
+use tokio::sync::Semaphore;
 
 /// Alias `Result` for convenience.
 pub type Result<T> = std::result::Result<T, error::Error>;
 
+/// Which API to fetch organisation-wide auditing data (admins, members,
+/// branch protection) from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// The paginated REST listings each audit used to fan out across on
+    /// its own. Slower (O(members + repos) requests), but useful as a
+    /// fallback where the GraphQL API isn't available (e.g. older GitHub
+    /// Enterprise instances).
+    Rest,
+    /// The single org-wide GraphQL query in [`graphql::fetch_org_data`].
+    /// (Default)
+    GraphQl,
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        Self::GraphQl
+    }
+}
+
 /// The auditor of a GitHub organisation.
 #[derive(Debug)]
 pub struct Auditor<'a> {
@@ -28,13 +55,30 @@ pub struct Auditor<'a> {
     organisation: serde_json::Value,
     /// Whether the auditor ran any audits in the last run.
     has_run_audit: bool,
+    /// The format results should be rendered in.
+    output_format: output::OutputFormat,
+    /// Whether warning-level findings should be treated as failures.
+    deny_warnings: bool,
+    /// The maximum number of requests to have in flight at once when
+    /// fanning out across a resource's pages.
+    concurrency: usize,
+    /// The on-disk cache of responses, keyed by URL, used to make
+    /// conditional requests. (Default: disabled)
+    cache: Option<cache::Cache>,
+    /// The base URL requests are made against. (Default:
+    /// `https://api.github.com`, for GitHub Enterprise instances this is
+    /// configurable via [`crate::AuditorBuilder::api_base_url`])
+    api_base_url: String,
+    /// Which API organisation-wide auditing data is fetched from. (Default:
+    /// [`Transport::GraphQl`])
+    transport: Transport,
 }
 
 impl<'a> Auditor<'a> {
     /// Perform the audit.
     /// # Errors
     /// If one of the audits has failed.
-    pub fn audit(&mut self) -> std::result::Result<(), Vec<error::Error>> {
+    pub async fn audit(&mut self) -> std::result::Result<(), Vec<error::Error>> {
         self.has_run_audit = false;
         let mut errors = Vec::new();
 
@@ -47,9 +91,33 @@ impl<'a> Auditor<'a> {
             };
         }
 
-        try_and_collect_errors!(self.audit_2fa());
-        try_and_collect_errors!(self.audit_admin_commit_activity());
-        try_and_collect_errors!(self.audit_all_master_branches_are_protected());
+        let org_data = if self.transport == Transport::GraphQl
+            && (self.config.enforces_2fa
+                || self.config.admins_have_no_commit_activity
+                || self.config.all_repos_master_is_protected
+                || self.config.admin_whitelist.is_some()
+                || self.config.member_whitelist.is_some())
+        {
+            match self.fetch_org_data().await {
+                Ok(org_data) => Some(org_data),
+                Err(error) => {
+                    Self::push_error(&mut errors, error);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        try_and_collect_errors!(self.audit_2fa(org_data.as_ref()).await);
+        try_and_collect_errors!(self.audit_admin_commit_activity(org_data.as_ref()).await);
+        try_and_collect_errors!(
+            self.audit_all_master_branches_are_protected(org_data.as_ref())
+                .await
+        );
+        self.audit_installed_apps(&mut errors).await;
+        self.audit_admins(org_data.as_ref(), &mut errors).await;
+        self.audit_members(org_data.as_ref(), &mut errors).await;
 
         if !self.has_run_audit {
             errors.push(error::Error::Audit {
@@ -64,18 +132,24 @@ impl<'a> Auditor<'a> {
         }
     }
 
-    /// Audit that 2fa is enforced for the organisation.
-    fn audit_2fa(&mut self) -> Result<()> {
+    /// Audit that 2fa is enforced for the organisation, using the 2FA
+    /// requirement from the single org-wide GraphQL query (see
+    /// [`Auditor::fetch_org_data`]), or the initial REST org blob if
+    /// [`Transport::Rest`] is selected.
+    async fn audit_2fa(&mut self, org_data: Option<&graphql::OrgData>) -> Result<()> {
         if !self.config.enforces_2fa {
             return Ok(());
         }
         self.mark_audit("2 Factor Authentication");
 
-        let enabled = self
-            .organisation
-            .get("two_factor_requirement_enabled")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let enabled = match (self.transport, org_data) {
+            (Transport::GraphQl, Some(org_data)) => org_data.requires_two_factor_authentication,
+            _ => self
+                .organisation
+                .get("two_factor_requirement_enabled")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        };
 
         if enabled {
             log::info!("✅ 2 Factor Authentication required for members");
@@ -87,39 +161,48 @@ impl<'a> Auditor<'a> {
         }
     }
 
-    /// Audit that all admin accounts have no push activity.
-    fn audit_admin_commit_activity(&mut self) -> Result<()> {
+    /// Audit that all admin accounts have no push activity. Admin logins
+    /// come from the single org-wide GraphQL query (see
+    /// [`Auditor::fetch_org_data`]), or the REST admin listing if
+    /// [`Transport::Rest`] is selected; GitHub has no GraphQL field for a
+    /// user's events, so whether they've pushed recently is still one
+    /// paginated REST listing (every page is fetched) per admin either way.
+    async fn audit_admin_commit_activity(
+        &mut self,
+        org_data: Option<&graphql::OrgData>,
+    ) -> Result<()> {
         if !self.config.admins_have_no_commit_activity {
             return Ok(());
         }
+        let admins = match self.transport {
+            Transport::GraphQl => match org_data {
+                Some(org_data) => org_data.admins.clone(),
+                None => return Ok(()),
+            },
+            Transport::Rest => self.fetch_admins_rest().await?,
+        };
         self.mark_audit("Admin Commit Activity");
 
-        let members_url = self
-            .organisation
-            .get("members_url")
-            .and_then(serde_json::Value::as_str)
-            .context(error::MissingGitHubData)?
-            .replace("{/member}", "?role=admin");
-
-        let members = self.get_all(members_url)?;
         let mut found_members = Vec::new();
 
-        for member in members {
-            let events_url = member
-                .get("events_url")
+        for admin in &admins {
+            let login = admin
+                .get("login")
                 .and_then(serde_json::Value::as_str)
-                .context(error::MissingGitHubData)?
-                .replace("{/privacy}", "");
-
-            let has_pushed = self.find(events_url, |e| {
-                e.get("type")
-                    .and_then(|v| v.as_str())
-                    .map(|t| t == "PushEvent")
-                    .unwrap_or(false)
-            })?;
+                .context(error::MissingGitHubData)?;
+            let events_url = format!("{}/users/{}/events", self.api_base_url, login);
+
+            let has_pushed = self
+                .find(events_url, |e| {
+                    e.get("type")
+                        .and_then(|v| v.as_str())
+                        .map(|t| t == "PushEvent")
+                        .unwrap_or(false)
+                })
+                .await?;
 
             if has_pushed.is_some() {
-                found_members.push(member);
+                found_members.push(admin.clone());
             }
         }
 
@@ -133,139 +216,405 @@ impl<'a> Auditor<'a> {
         }
     }
 
-    fn audit_all_master_branches_are_protected(&mut self) -> Result<()> {
+    /// Audit that every repository's master branch is protected, using the
+    /// branch protection data from the single org-wide GraphQL query (see
+    /// [`Auditor::fetch_org_data`]), or the REST branch listing of each
+    /// repository if [`Transport::Rest`] is selected.
+    async fn audit_all_master_branches_are_protected(
+        &mut self,
+        org_data: Option<&graphql::OrgData>,
+    ) -> Result<()> {
         if !self.config.all_repos_master_is_protected {
             return Ok(());
         }
+        let unprotected_repos = match self.transport {
+            Transport::GraphQl => match org_data {
+                Some(org_data) => org_data.unprotected_repos.clone(),
+                None => return Ok(()),
+            },
+            Transport::Rest => self.fetch_unprotected_repos_rest().await?,
+        };
 
         self.mark_audit("Protected master branches.");
-        let mut unprotected_repos = Vec::new();
 
-        let repos_url = self
+        if unprotected_repos.is_empty() {
+            log::info!("✅ All master branches are protected");
+            Ok(())
+        } else {
+            Err(error::Error::Audit {
+                kind: error::AuditError::UnProtectedMasterBranches(unprotected_repos),
+            })
+        }
+    }
+
+    /// Audit the organisation's installed GitHub Apps against
+    /// `installed_app_whitelist`, if configured.
+    async fn audit_installed_apps(&mut self, errors: &mut Vec<error::Error>) {
+        let whitelist = match self.config.installed_app_whitelist.clone() {
+            Some(whitelist) => whitelist,
+            None => return,
+        };
+        self.mark_audit("Installed application whitelist");
+
+        let org_login = match self
             .organisation
-            .get("repos_url")
+            .get("login")
             .and_then(serde_json::Value::as_str)
-            .context(error::MissingGitHubData)?;
+        {
+            Some(login) => login.to_owned(),
+            None => return Self::push_error(errors, error::Error::MissingGitHubData),
+        };
+
+        let url = format!("{}/orgs/{}/installations", self.api_base_url, org_login);
+        let installations = match self.get_all_nested(url, "installations").await {
+            Ok(installations) => installations,
+            Err(error) => return Self::push_error(errors, error),
+        };
+
+        let (unexpected, missing) = Self::diff_whitelist(&installations, &whitelist, "app_slug");
+        Self::record_whitelist_diff(
+            errors,
+            unexpected,
+            missing,
+            error::AuditError::UnexpectedApps,
+            error::AuditError::MissingApps,
+            "Installed applications match the whitelist",
+        );
+    }
 
-        for repo in self.get_all(repos_url)? {
-            let branches_url = repo
-                .get("branches_url")
-                .and_then(serde_json::Value::as_str)
-                .context(error::MissingGitHubData)?
-                .replace("{/branch}", "?protected=false");
+    /// Audit the organisation's admins against `admin_whitelist`, if
+    /// configured. Admin logins come from the single org-wide GraphQL
+    /// query (see [`Auditor::fetch_org_data`]), or the REST admin listing
+    /// if [`Transport::Rest`] is selected.
+    async fn audit_admins(
+        &mut self,
+        org_data: Option<&graphql::OrgData>,
+        errors: &mut Vec<error::Error>,
+    ) {
+        let whitelist = match self.config.admin_whitelist.clone() {
+            Some(whitelist) => whitelist,
+            None => return,
+        };
+        let admins = match self.transport {
+            Transport::GraphQl => match org_data {
+                Some(org_data) => org_data.admins.clone(),
+                None => return,
+            },
+            Transport::Rest => match self.fetch_admins_rest().await {
+                Ok(admins) => admins,
+                Err(error) => return Self::push_error(errors, error),
+            },
+        };
+        self.mark_audit("Admin whitelist");
+
+        let (unexpected, missing) = Self::diff_whitelist(&admins, &whitelist, "login");
+        Self::record_whitelist_diff(
+            errors,
+            unexpected,
+            missing,
+            error::AuditError::UnexpectedAdmins,
+            error::AuditError::MissingAdmins,
+            "Admins match the whitelist",
+        );
+    }
 
-            let master = self.find(branches_url, |r| {
-                r.get("name").map(|n| n == "master").unwrap_or(false)
-            })?;
+    /// Audit the organisation's members against `member_whitelist`, if
+    /// configured. Member logins come from the single org-wide GraphQL
+    /// query (see [`Auditor::fetch_org_data`]), or the REST member listing
+    /// if [`Transport::Rest`] is selected.
+    async fn audit_members(
+        &mut self,
+        org_data: Option<&graphql::OrgData>,
+        errors: &mut Vec<error::Error>,
+    ) {
+        let whitelist = match self.config.member_whitelist.clone() {
+            Some(whitelist) => whitelist,
+            None => return,
+        };
+        let members = match self.transport {
+            Transport::GraphQl => match org_data {
+                Some(org_data) => org_data.members.clone(),
+                None => return,
+            },
+            Transport::Rest => match self.fetch_members_rest().await {
+                Ok(members) => members,
+                Err(error) => return Self::push_error(errors, error),
+            },
+        };
+        self.mark_audit("Member whitelist");
+
+        let (unexpected, missing) = Self::diff_whitelist(&members, &whitelist, "login");
+        Self::record_whitelist_diff(
+            errors,
+            unexpected,
+            missing,
+            error::AuditError::UnexpectedMembers,
+            error::AuditError::MissingMembers,
+            "Members match the whitelist",
+        );
+    }
 
-            let is_unprotected = master
-                .and_then(|b| b.get("protected").and_then(serde_json::Value::as_bool))
-                .map(|b| !b)
-                .unwrap_or(true);
+    /// Diff the `key` field of `observed` entities against `whitelist`,
+    /// returning `(unexpected, missing)` entries.
+    fn diff_whitelist(
+        observed: &[serde_json::Value],
+        whitelist: &[String],
+        key: &str,
+    ) -> (Vec<String>, Vec<String>) {
+        use std::collections::HashSet;
+
+        let observed: HashSet<&str> = observed
+            .iter()
+            .filter_map(|v| v.get(key).and_then(serde_json::Value::as_str))
+            .collect();
+        let whitelist: HashSet<&str> = whitelist.iter().map(String::as_str).collect();
+
+        let unexpected = observed
+            .difference(&whitelist)
+            .map(|s| (*s).to_owned())
+            .collect();
+        let missing = whitelist
+            .difference(&observed)
+            .map(|s| (*s).to_owned())
+            .collect();
+
+        (unexpected, missing)
+    }
 
-            if is_unprotected {
-                unprotected_repos.push(repo);
-            }
+    /// Push both the "unexpected" and "missing" findings of a whitelist diff
+    /// into `errors`, if any, logging a success message if neither occurred.
+    fn record_whitelist_diff(
+        errors: &mut Vec<error::Error>,
+        unexpected: Vec<String>,
+        missing: Vec<String>,
+        unexpected_kind: impl FnOnce(Vec<String>) -> error::AuditError,
+        missing_kind: impl FnOnce(Vec<String>) -> error::AuditError,
+        success_msg: &str,
+    ) {
+        if unexpected.is_empty() && missing.is_empty() {
+            log::info!("✅ {}", success_msg);
+            return;
         }
 
-        if unprotected_repos.is_empty() {
-            log::info!("✅ All master branches are protected");
-            Ok(())
-        } else {
-            Err(error::Error::Audit {
-                kind: error::AuditError::UnProtectedMasterBranches(unprotected_repos),
-            })
+        if !unexpected.is_empty() {
+            Self::push_error(
+                errors,
+                error::Error::Audit {
+                    kind: unexpected_kind(unexpected),
+                },
+            );
+        }
+
+        if !missing.is_empty() {
+            Self::push_error(
+                errors,
+                error::Error::Audit {
+                    kind: missing_kind(missing),
+                },
+            );
         }
     }
 
+    /// Log and push a single error onto `errors`.
+    fn push_error(errors: &mut Vec<error::Error>, error: error::Error) {
+        log::error!("{}", error);
+        errors.push(error);
+    }
+
     /// Whether the `Auditor` has run at least one auditing procedure.
     pub fn has_run(&self) -> bool {
         self.has_run_audit
     }
 
-    /// Find the first entity that matches `pred`, if any match. Goes through
-    /// GitHub's pagination so will make potentially make multiple requests.
-    fn find(
+    /// The format [`Auditor::audit`]'s results should be rendered in.
+    pub fn output_format(&self) -> output::OutputFormat {
+        self.output_format
+    }
+
+    /// Whether warning-level findings should be treated as failures.
+    pub fn deny_warnings(&self) -> bool {
+        self.deny_warnings
+    }
+
+    /// Find the first entity that matches `pred`, if any match. Fetches
+    /// every page of the resource (see [`Auditor::get_all`]) since GitHub
+    /// gives us no way to search server-side.
+    async fn find(
         &self,
         url: String,
-        pred: impl FnMut(&&serde_json::Value) -> bool + Copy,
+        pred: impl FnMut(&&serde_json::Value) -> bool,
     ) -> Result<Option<serde_json::Value>> {
-        let mut next = Some(url);
-
-        while let Some(url) = next {
-            let mut response = self
-                .client
-                .get(&url)
-                .bearer_auth(&self.auth_key)
-                .send()
-                .context(error::Http)?;
-
-            next = response
-                .headers()
-                .decode::<hyperx::header::Link>()
-                .ok()
-                .and_then(|v| {
-                    v.values()
-                        .iter()
-                        .find(|link| {
-                            link.rel()
-                                .map(|rel| rel.contains(&RelationType::Next))
-                                .unwrap_or(false)
-                        })
-                        .map(|l| l.link())
-                        .map(str::to_owned)
-                });
-
-            let json = response.json::<serde_json::Value>().context(error::Http)?;
-
-            let item = json.as_array().and_then(|v| v.iter().find(pred));
-
-            if let Some(item) = item {
-                return Ok(Some(item.clone()));
-            }
-        }
+        Ok(self.get_all(url).await?.iter().find(pred).cloned())
+    }
+
+    /// Gets all entries across all pages from a resource in GitHub, where
+    /// each page's response body is itself a JSON array.
+    async fn get_all<'b, I: Into<Cow<'b, str>>>(&self, url: I) -> Result<Vec<serde_json::Value>> {
+        self.get_all_by(url, |body| {
+            body.as_array().context(error::MissingGitHubData).cloned()
+        })
+        .await
+    }
 
-        Ok(None)
+    /// Gets all entries across all pages from a resource in GitHub whose
+    /// response body wraps the array under `key`, e.g. `GET
+    /// /orgs/:org/installations`'s `{"total_count": ..., "installations":
+    /// [...]}`.
+    async fn get_all_nested<'b, I: Into<Cow<'b, str>>>(
+        &self,
+        url: I,
+        key: &str,
+    ) -> Result<Vec<serde_json::Value>> {
+        self.get_all_by(url, |body| {
+            body.get(key)
+                .and_then(serde_json::Value::as_array)
+                .context(error::MissingGitHubData)
+                .cloned()
+        })
+        .await
     }
 
-    /// Gets a all entries across all pages from a resource in GitHub.
-    fn get_all<'b, I: Into<Cow<'b, str>>>(&self, url: I) -> Result<Vec<serde_json::Value>> {
-        let mut entities = Vec::new();
-        let mut next = Some(url.into());
-
-        while let Some(url) = next {
-            let mut response = self
-                .client
-                .get(&*url)
-                .bearer_auth(&self.auth_key)
-                .send()
-                .context(error::Http)?;
-
-            next = response
-                .headers()
-                .decode::<hyperx::header::Link>()
-                .ok()
-                .and_then(|v| {
-                    v.values()
-                        .iter()
-                        .find(|link| {
-                            link.rel()
-                                .map(|rel| rel.contains(&RelationType::Next))
-                                .unwrap_or(false)
-                        })
-                        .map(|l| l.link())
-                        .map(str::to_owned)
-                        .map(Cow::Owned)
-                });
-
-            let json = response.json::<serde_json::Value>().context(error::Http)?;
-
-            entities.extend_from_slice(&json.as_array().context(error::MissingGitHubData)?);
+    /// Gets all entries across all pages from a resource in GitHub,
+    /// extracting each page's entries from its response body with
+    /// `extract`.
+    ///
+    /// The first page is fetched to discover how many pages the resource
+    /// has (via the `last` relation of its `Link` header); the remaining
+    /// pages are then fetched concurrently, bounded by `self.concurrency`
+    /// requests in flight at once, to avoid the O(pages) latency of
+    /// fetching one page at a time.
+    async fn get_all_by<'b, I: Into<Cow<'b, str>>>(
+        &self,
+        url: I,
+        extract: impl Fn(&serde_json::Value) -> Result<Vec<serde_json::Value>>,
+    ) -> Result<Vec<serde_json::Value>> {
+        let first_url = url.into().into_owned();
+        let first_page = http::fetch_page(
+            &*self.client,
+            &self.auth_key,
+            &first_url,
+            self.cache.as_ref(),
+        )
+        .await?;
+
+        let mut entities = extract(&first_page.body)?;
+
+        let last_url = match first_page.link {
+            Some(last_url) => last_url,
+            None => return Ok(entities),
+        };
+
+        let last_page = http::page_number(&last_url).context(error::MissingGitHubData)?;
+        let semaphore = Semaphore::new(self.concurrency);
+
+        let mut pages: FuturesUnordered<_> = (2..=last_page)
+            .map(|page| http::with_page(&last_url, page).context(error::MissingGitHubData))
+            .map(|url| async {
+                let url = url?;
+                let _permit = semaphore.acquire().await;
+                http::fetch_page(&*self.client, &self.auth_key, &url, self.cache.as_ref()).await
+            })
+            .collect();
+
+        while let Some(page) = pages.next().await {
+            entities.extend(extract(&page?.body)?);
         }
 
         Ok(entities)
     }
 
+    /// Fetch member roles and repository branch protection for the whole
+    /// organisation in a single GraphQL query (see [`graphql::fetch_org_data`]),
+    /// instead of the separate REST listing each of
+    /// [`Auditor::audit_admin_commit_activity`],
+    /// [`Auditor::audit_all_master_branches_are_protected`],
+    /// [`Auditor::audit_admins`], and [`Auditor::audit_members`] used to
+    /// make on their own.
+    async fn fetch_org_data(&self) -> Result<graphql::OrgData> {
+        let org_login = self
+            .organisation
+            .get("login")
+            .and_then(serde_json::Value::as_str)
+            .context(error::MissingGitHubData)?;
+
+        graphql::fetch_org_data(&self.client, &self.auth_key, &self.api_base_url, org_login).await
+    }
+
+    /// Fetch the organisation's admins via the REST members listing,
+    /// filtered to `role=admin`. The [`Transport::Rest`] fallback for the
+    /// admin logins [`Auditor::fetch_org_data`] otherwise provides.
+    async fn fetch_admins_rest(&self) -> Result<Vec<serde_json::Value>> {
+        let members_url = self
+            .organisation
+            .get("members_url")
+            .and_then(serde_json::Value::as_str)
+            .context(error::MissingGitHubData)?
+            .replace("{/member}", "?role=admin");
+
+        self.get_all(members_url).await
+    }
+
+    /// Fetch the organisation's members via the REST members listing. The
+    /// [`Transport::Rest`] fallback for the member logins
+    /// [`Auditor::fetch_org_data`] otherwise provides.
+    async fn fetch_members_rest(&self) -> Result<Vec<serde_json::Value>> {
+        let members_url = self
+            .organisation
+            .get("members_url")
+            .and_then(serde_json::Value::as_str)
+            .context(error::MissingGitHubData)?
+            .replace("{/member}", "");
+
+        self.get_all(members_url).await
+    }
+
+    /// Fetch every repository whose `master` branch is unprotected, by
+    /// listing each repository's branches via REST and checking the
+    /// `master` branch's own `protected` field. Repositories with no
+    /// `master` branch at all (e.g. an empty repository) aren't flagged,
+    /// matching [`graphql::fetch_org_data`]. The [`Transport::Rest`]
+    /// fallback for the branch protection data [`Auditor::fetch_org_data`]
+    /// otherwise provides.
+    async fn fetch_unprotected_repos_rest(&self) -> Result<Vec<serde_json::Value>> {
+        let repos_url = self
+            .organisation
+            .get("repos_url")
+            .and_then(serde_json::Value::as_str)
+            .context(error::MissingGitHubData)?;
+
+        let mut unprotected_repos = Vec::new();
+
+        for repo in self.get_all(repos_url).await? {
+            let branches_url = repo
+                .get("branches_url")
+                .and_then(serde_json::Value::as_str)
+                .context(error::MissingGitHubData)?
+                .replace("{/branch}", "");
+
+            let master = match self
+                .find(branches_url, |r| {
+                    r.get("name").map(|n| n == "master").unwrap_or(false)
+                })
+                .await?
+            {
+                Some(master) => master,
+                // No `master` branch at all: nothing to protect.
+                None => continue,
+            };
+
+            let is_protected = master
+                .get("protected")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false);
+
+            if !is_protected {
+                unprotected_repos.push(repo);
+            }
+        }
+
+        Ok(unprotected_repos)
+    }
+
     /// Convenience method to mark that at least one audit was performed on
     /// the repo.
     fn mark_audit(&mut self, msg: &str) {
@@ -1,6 +1,7 @@
 use std::io::Write;
 
 use clap::clap_app;
+use gh_auditor::{OutputFormat, Severity};
 
 fn try_or_exit<T>(result: gh_auditor::Result<T>, num: i32) -> T {
     match result {
@@ -14,13 +15,36 @@ fn try_or_exit<T>(result: gh_auditor::Result<T>, num: i32) -> T {
 
 const LOG_LEVEL: &str = "info";
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// An error occurred outside of an audit itself, e.g. authentication or a
+/// failed HTTP request.
+const EXIT_OPERATIONAL_ERROR: i32 = -1;
+/// At least one finding at or above the configured severity threshold.
+const EXIT_AUDIT_FAILURE: i32 = -2;
+/// Findings were present, but none met the configured severity threshold,
+/// so the run isn't a failure — only worth flagging to a CI consumer that
+/// wants to distinguish this from a fully clean audit.
+const EXIT_WARNINGS_ONLY: i32 = -3;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = clap_app!(gh_auditor =>
         (author: "Erin P. <xampprocky@gmail.com>")
         (@arg organisation: +takes_value +required
         "GitHub Organisation to audit. Requires `admin:read` level permissions")
         (@arg token: -t --token +takes_value
         "GitHub authentication token.")
+        (@arg format: -f --format +takes_value
+        "Output format for audit results: human, json, or sarif. (Default: human)")
+        (@arg deny_warnings: --("deny-warnings")
+        "Treat warning-level findings as failures, not just error-level findings.")
+        (@arg cache_dir: --("cache-dir") +takes_value
+        "Directory to cache API responses in, reused across runs via conditional requests.")
+        (@arg api_base_url: --("api-base-url") +takes_value
+        "API base URL to audit, for GitHub Enterprise instances. (Default: https://api.github.com)")
+        (@arg ca_cert: --("ca-cert") +takes_value
+        "Path to a custom CA certificate (PEM) to trust, for GitHub Enterprise instances.")
+        (@arg config: -c --config +takes_value
+        "Path to a TOML or YAML policy file to load configuration from. (Default: built-in defaults)")
     )
     .get_matches();
 
@@ -28,16 +52,70 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .format(|buf, record| writeln!(buf, "{}", record.args()))
         .init();
 
-    let mut builder = gh_auditor::AuditorBuilder::new(matches.value_of("organisation").unwrap());
+    let output_format = match matches.value_of("format") {
+        Some(format) => format.parse::<OutputFormat>().unwrap_or_else(|error| {
+            log::error!("{}", error);
+            std::process::exit(EXIT_OPERATIONAL_ERROR)
+        }),
+        None => OutputFormat::default(),
+    };
+
+    let mut builder = gh_auditor::AuditorBuilder::new(matches.value_of("organisation").unwrap())
+        .output_format(output_format)
+        .deny_warnings(matches.is_present("deny_warnings"));
+
+    if let Some(path) = matches.value_of("config") {
+        let config = try_or_exit(gh_auditor::Config::from_path(path), EXIT_OPERATIONAL_ERROR);
+        builder = builder.config(config);
+    }
 
     if let Some(key) = matches.value_of("token") {
         builder = builder.auth_key(key);
     }
 
-    let mut auditor = try_or_exit(builder.finish(), -1);
+    if let Some(dir) = matches.value_of("cache_dir") {
+        builder = builder.cache_dir(dir);
+    }
+
+    if let Some(api_base_url) = matches.value_of("api_base_url") {
+        builder = builder.api_base_url(api_base_url);
+    }
+
+    if let Some(ca_cert) = matches.value_of("ca_cert") {
+        builder = builder.ca_cert(ca_cert);
+    }
+
+    let mut auditor = try_or_exit(builder.finish().await, EXIT_OPERATIONAL_ERROR);
+
+    let errors = auditor.audit().await.err().unwrap_or_default();
+    let findings: Vec<_> = errors
+        .iter()
+        .filter_map(|error| error.as_finding())
+        .collect();
+
+    match auditor.output_format() {
+        OutputFormat::Human => {}
+        OutputFormat::Json => println!("{}", gh_auditor::to_json(&findings)?),
+        OutputFormat::Sarif => println!("{}", gh_auditor::to_sarif(&findings)?),
+    }
+
+    let is_operational_error = findings.len() != errors.len();
+    if is_operational_error {
+        std::process::exit(EXIT_OPERATIONAL_ERROR);
+    }
+
+    let threshold = if auditor.deny_warnings() {
+        Severity::Warning
+    } else {
+        Severity::Error
+    };
+
+    if findings.iter().any(|f| f.severity.meets(threshold)) {
+        std::process::exit(EXIT_AUDIT_FAILURE);
+    }
 
-    if auditor.audit().is_err() {
-        std::process::exit(-2);
+    if !findings.is_empty() {
+        std::process::exit(EXIT_WARNINGS_ONLY);
     }
 
     Ok(())
@@ -0,0 +1,159 @@
+//! Machine-readable representations of audit results, for consumption by
+//! CI systems and other tooling.
+
+use serde::Serialize;
+
+use crate::error;
+
+/// The format audit results should be rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable log lines (the default).
+    Human,
+    /// A JSON array of [`Finding`]s.
+    Json,
+    /// A [SARIF](https://sarifweb.azurewebsites.net/) log, suitable for
+    /// upload to GitHub's code-scanning UI.
+    Sarif,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Human
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            other => Err(format!(
+                "invalid output format '{}', expected one of: human, json, sarif",
+                other
+            )),
+        }
+    }
+}
+
+/// The severity of an [`error::AuditError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// A finding that should be treated as a hard failure.
+    Error,
+    /// A finding that is worth surfacing, but not necessarily blocking.
+    Warning,
+}
+
+impl Severity {
+    /// Whether this severity should be treated as a failure under the given
+    /// `threshold`, e.g. a `Warning` only meets a `Warning` threshold, while
+    /// an `Error` meets either.
+    pub fn meets(self, threshold: Self) -> bool {
+        match threshold {
+            Self::Warning => true,
+            Self::Error => self == Self::Error,
+        }
+    }
+}
+
+/// A structured, serializable representation of an [`error::AuditError`].
+#[derive(Debug, Serialize)]
+pub struct Finding {
+    /// The stable identifier of the audit that produced this finding, e.g.
+    /// `enforces_2fa`.
+    pub id: &'static str,
+    /// How severe this finding is.
+    pub severity: Severity,
+    /// The offending entities (admin logins, repository `full_name`s, etc.)
+    /// collected during the audit.
+    pub entities: Vec<String>,
+    /// The recommendation to resolve the finding, as shown in `Display`.
+    pub recommendation: &'static str,
+}
+
+/// Render `findings` as a pretty-printed JSON array.
+/// # Errors
+/// If the findings could not be serialized.
+pub fn to_json(findings: &[Finding]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(findings)
+}
+
+/// Render `findings` as a [SARIF](https://sarifweb.azurewebsites.net/) log.
+/// # Errors
+/// If the resulting document could not be serialized.
+pub fn to_sarif(findings: &[Finding]) -> serde_json::Result<String> {
+    let rules: Vec<_> = {
+        let mut ids: Vec<_> = findings.iter().map(|f| f.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.into_iter()
+            .map(|id| serde_json::json!({ "id": id }))
+            .collect()
+    };
+
+    let results: Vec<_> = findings
+        .iter()
+        .map(|finding| {
+            let message = if finding.entities.is_empty() {
+                finding.recommendation.to_owned()
+            } else {
+                format!(
+                    "{} ({})",
+                    finding.recommendation,
+                    finding.entities.join(", ")
+                )
+            };
+
+            serde_json::json!({
+                "ruleId": finding.id,
+                "level": match finding.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                "message": { "text": message },
+                // Findings are organisation-wide (admins, repos, members),
+                // not tied to a line in a file, but code-scanning requires
+                // at least one location to display a result; point at the
+                // repo root as a placeholder.
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "." },
+                        "region": { "startLine": 1 },
+                    },
+                }],
+            })
+        })
+        .collect();
+
+    let sarif = serde_json::json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "gh-auditor",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string_pretty(&sarif)
+}
+
+impl error::Error {
+    /// The structured [`Finding`] this error represents, if it is an audit
+    /// finding rather than an operational error (e.g. an HTTP failure).
+    pub fn as_finding(&self) -> Option<Finding> {
+        match self {
+            Self::Audit { kind } => Some(kind.to_finding()),
+            _ => None,
+        }
+    }
+}